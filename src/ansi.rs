@@ -0,0 +1,374 @@
+use eframe::egui;
+
+use crate::theme::Theme;
+
+/// A single styled character cell in the output grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub ch: char,
+    pub fg: egui::Color32,
+    pub bg: egui::Color32,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl Cell {
+    fn new(ch: char, fg: egui::Color32, bg: egui::Color32, bold: bool, italic: bool) -> Self {
+        Self { ch, fg, bg, bold, italic }
+    }
+}
+
+/// One line of the rendered grid.
+#[derive(Debug, Clone, Default)]
+pub struct Row {
+    pub cells: Vec<Cell>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Ground,
+    Escape,
+    CsiEntry,
+    CsiParam,
+    CsiIntermediate,
+    OscString,
+}
+
+/// A Paul Williams-style state machine that turns a raw ANSI/VTE byte
+/// stream into a grid of styled `Cell`s, resolving SGR colors through the
+/// active `Theme` so command output (`ls --color`, `git`, `cargo`) renders
+/// with real colors instead of raw escape bytes.
+pub struct AnsiParser {
+    state: State,
+    params: Vec<u16>,
+    current_param: Option<u16>,
+    osc_seen_esc: bool,
+    rows: Vec<Row>,
+    cursor_row: usize,
+    cursor_col: usize,
+    fg: egui::Color32,
+    bg: egui::Color32,
+    bold: bool,
+    italic: bool,
+    default_fg: egui::Color32,
+    default_bg: egui::Color32,
+    palette: [egui::Color32; 16],
+}
+
+impl AnsiParser {
+    pub fn new(theme: &Theme) -> Self {
+        Self {
+            state: State::Ground,
+            params: Vec::new(),
+            current_param: None,
+            osc_seen_esc: false,
+            rows: vec![Row::default()],
+            cursor_row: 0,
+            cursor_col: 0,
+            fg: theme.foreground,
+            bg: theme.background,
+            bold: false,
+            italic: false,
+            default_fg: theme.foreground,
+            default_bg: theme.background,
+            palette: Self::palette_for(theme),
+        }
+    }
+
+    /// The 16 base ANSI colors, with black/white/blue tinted by the active
+    /// theme so palettes stay consistent with the rest of the UI.
+    fn palette_for(theme: &Theme) -> [egui::Color32; 16] {
+        let mut palette = [
+            egui::Color32::from_rgb(0, 0, 0),
+            egui::Color32::from_rgb(205, 49, 49),
+            egui::Color32::from_rgb(13, 188, 121),
+            egui::Color32::from_rgb(229, 229, 16),
+            egui::Color32::from_rgb(36, 114, 200),
+            egui::Color32::from_rgb(188, 63, 188),
+            egui::Color32::from_rgb(17, 168, 205),
+            egui::Color32::from_rgb(229, 229, 229),
+            egui::Color32::from_rgb(102, 102, 102),
+            egui::Color32::from_rgb(241, 76, 76),
+            egui::Color32::from_rgb(35, 209, 139),
+            egui::Color32::from_rgb(245, 245, 67),
+            egui::Color32::from_rgb(59, 142, 234),
+            egui::Color32::from_rgb(214, 112, 214),
+            egui::Color32::from_rgb(41, 184, 219),
+            egui::Color32::from_rgb(229, 229, 229),
+        ];
+        palette[0] = theme.background;
+        palette[7] = theme.foreground;
+        palette[15] = theme.foreground;
+        palette[4] = theme.accent;
+        palette[12] = theme.accent;
+        palette
+    }
+
+    pub fn feed(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.advance(byte);
+        }
+    }
+
+    pub fn into_rows(self) -> Vec<Row> {
+        self.rows
+    }
+
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    /// Append already-styled rows (e.g. from a one-shot command run) and
+    /// drop the cursor onto a fresh line below them.
+    pub fn append_rows(&mut self, rows: Vec<Row>) {
+        self.rows.extend(rows);
+        self.rows.push(Row::default());
+        self.cursor_row = self.rows.len() - 1;
+        self.cursor_col = 0;
+    }
+
+    fn advance(&mut self, byte: u8) {
+        match self.state {
+            State::Ground => self.ground(byte),
+            State::Escape => self.escape(byte),
+            State::CsiEntry | State::CsiParam | State::CsiIntermediate => self.csi(byte),
+            State::OscString => self.osc(byte),
+        }
+    }
+
+    fn ground(&mut self, byte: u8) {
+        match byte {
+            0x1b => self.state = State::Escape,
+            b'\n' => self.newline(),
+            b'\r' => self.cursor_col = 0,
+            0x08 => self.cursor_col = self.cursor_col.saturating_sub(1),
+            _ => {
+                if let Some(ch) = Self::printable(byte) {
+                    self.put_char(ch);
+                }
+            }
+        }
+    }
+
+    fn escape(&mut self, byte: u8) {
+        match byte {
+            b'[' => {
+                self.params.clear();
+                self.current_param = None;
+                self.state = State::CsiEntry;
+            }
+            b']' => {
+                self.osc_seen_esc = false;
+                self.state = State::OscString;
+            }
+            _ => self.state = State::Ground,
+        }
+    }
+
+    fn csi(&mut self, byte: u8) {
+        match byte {
+            b'0'..=b'9' => {
+                let digit = (byte - b'0') as u16;
+                let accumulated = self.current_param.unwrap_or(0).saturating_mul(10).saturating_add(digit);
+                self.current_param = Some(accumulated);
+                self.state = State::CsiParam;
+            }
+            b';' => self.params.push(self.current_param.take().unwrap_or(0)),
+            0x20..=0x2f => self.state = State::CsiIntermediate,
+            0x40..=0x7e => {
+                self.params.push(self.current_param.take().unwrap_or(0));
+                self.dispatch_csi(byte as char);
+                self.state = State::Ground;
+            }
+            _ => self.state = State::Ground,
+        }
+    }
+
+    fn osc(&mut self, byte: u8) {
+        match byte {
+            0x07 => self.state = State::Ground,
+            0x1b => self.osc_seen_esc = true,
+            b'\\' if self.osc_seen_esc => self.state = State::Ground,
+            _ => self.osc_seen_esc = false,
+        }
+    }
+
+    fn dispatch_csi(&mut self, final_byte: char) {
+        let params = std::mem::take(&mut self.params);
+        let param = |i: usize, default: u16| params.get(i).copied().filter(|&p| p != 0).unwrap_or(default);
+
+        match final_byte {
+            'm' => self.sgr(&params),
+            'H' | 'f' => {
+                self.cursor_row = param(0, 1) as usize - 1;
+                self.cursor_col = param(1, 1) as usize - 1;
+                self.ensure_row(self.cursor_row);
+            }
+            'J' => self.erase_display(params.first().copied().unwrap_or(0)),
+            'K' => self.erase_line(params.first().copied().unwrap_or(0)),
+            'A' => self.cursor_row = self.cursor_row.saturating_sub(param(0, 1) as usize),
+            'B' => {
+                self.cursor_row += param(0, 1) as usize;
+                self.ensure_row(self.cursor_row);
+            }
+            'C' => self.cursor_col += param(0, 1) as usize,
+            'D' => self.cursor_col = self.cursor_col.saturating_sub(param(0, 1) as usize),
+            'E' => {
+                self.cursor_row += param(0, 1) as usize;
+                self.cursor_col = 0;
+                self.ensure_row(self.cursor_row);
+            }
+            'F' => {
+                self.cursor_row = self.cursor_row.saturating_sub(param(0, 1) as usize);
+                self.cursor_col = 0;
+            }
+            'G' => self.cursor_col = param(0, 1) as usize - 1,
+            _ => {}
+        }
+    }
+
+    fn sgr(&mut self, params: &[u16]) {
+        if params.is_empty() {
+            self.reset_style();
+            return;
+        }
+
+        let mut i = 0;
+        while i < params.len() {
+            match params[i] {
+                0 => self.reset_style(),
+                1 => self.bold = true,
+                3 => self.italic = true,
+                22 => self.bold = false,
+                23 => self.italic = false,
+                39 => self.fg = self.default_fg,
+                49 => self.bg = self.default_bg,
+                n @ 30..=37 => self.fg = self.palette[(n - 30) as usize],
+                n @ 90..=97 => self.fg = self.palette[(n - 90 + 8) as usize],
+                n @ 40..=47 => self.bg = self.palette[(n - 40) as usize],
+                n @ 100..=107 => self.bg = self.palette[(n - 100 + 8) as usize],
+                n @ (38 | 48) => {
+                    let is_fg = n == 38;
+                    match params.get(i + 1) {
+                        Some(5) => {
+                            if let Some(&idx) = params.get(i + 2) {
+                                let color = self.color_256(idx as u8);
+                                if is_fg { self.fg = color } else { self.bg = color }
+                            }
+                            i += 2;
+                        }
+                        Some(2) => {
+                            let r = params.get(i + 2).copied().unwrap_or(0) as u8;
+                            let g = params.get(i + 3).copied().unwrap_or(0) as u8;
+                            let b = params.get(i + 4).copied().unwrap_or(0) as u8;
+                            let color = egui::Color32::from_rgb(r, g, b);
+                            if is_fg { self.fg = color } else { self.bg = color }
+                            i += 4;
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    fn color_256(&self, idx: u8) -> egui::Color32 {
+        match idx {
+            0..=15 => self.palette[idx as usize],
+            16..=231 => {
+                let idx = idx - 16;
+                let scale = |c: u8| if c == 0 { 0 } else { 55 + c * 40 };
+                egui::Color32::from_rgb(scale(idx / 36), scale((idx % 36) / 6), scale(idx % 6))
+            }
+            _ => {
+                let level = 8 + (idx - 232) * 10;
+                egui::Color32::from_rgb(level, level, level)
+            }
+        }
+    }
+
+    fn reset_style(&mut self) {
+        self.fg = self.default_fg;
+        self.bg = self.default_bg;
+        self.bold = false;
+        self.italic = false;
+    }
+
+    fn printable(byte: u8) -> Option<char> {
+        if byte >= 0x20 && byte != 0x7f {
+            Some(byte as char)
+        } else {
+            None
+        }
+    }
+
+    fn ensure_row(&mut self, row: usize) {
+        while self.rows.len() <= row {
+            self.rows.push(Row::default());
+        }
+    }
+
+    fn newline(&mut self) {
+        self.cursor_row += 1;
+        self.cursor_col = 0;
+        self.ensure_row(self.cursor_row);
+    }
+
+    fn put_char(&mut self, ch: char) {
+        self.ensure_row(self.cursor_row);
+        let row = &mut self.rows[self.cursor_row];
+        while row.cells.len() <= self.cursor_col {
+            row.cells.push(Cell::new(' ', self.default_fg, self.default_bg, false, false));
+        }
+        row.cells[self.cursor_col] = Cell::new(ch, self.fg, self.bg, self.bold, self.italic);
+        self.cursor_col += 1;
+    }
+
+    fn erase_display(&mut self, mode: u16) {
+        match mode {
+            0 => {
+                self.ensure_row(self.cursor_row);
+                self.rows[self.cursor_row].cells.truncate(self.cursor_col);
+                self.rows.truncate(self.cursor_row + 1);
+            }
+            1 => {
+                for row in &mut self.rows[..self.cursor_row] {
+                    row.cells.clear();
+                }
+                if let Some(row) = self.rows.get_mut(self.cursor_row) {
+                    for cell in row.cells.iter_mut().take(self.cursor_col) {
+                        *cell = Cell::new(' ', self.default_fg, self.default_bg, false, false);
+                    }
+                }
+            }
+            _ => {
+                self.rows = vec![Row::default()];
+                self.cursor_row = 0;
+                self.cursor_col = 0;
+            }
+        }
+    }
+
+    fn erase_line(&mut self, mode: u16) {
+        self.ensure_row(self.cursor_row);
+        let row = &mut self.rows[self.cursor_row];
+        match mode {
+            0 => row.cells.truncate(self.cursor_col),
+            1 => {
+                for cell in row.cells.iter_mut().take(self.cursor_col) {
+                    *cell = Cell::new(' ', self.default_fg, self.default_bg, false, false);
+                }
+            }
+            _ => row.cells.clear(),
+        }
+    }
+}
+
+/// Parse a full byte stream into a styled grid in one shot.
+pub fn parse(bytes: &[u8], theme: &Theme) -> Vec<Row> {
+    let mut parser = AnsiParser::new(theme);
+    parser.feed(bytes);
+    parser.into_rows()
+}
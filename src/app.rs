@@ -9,13 +9,16 @@ pub struct IgnisApp {
 
 impl IgnisApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        let theme = ThemeManager::load_default();
+        let theme = ThemeManager::load();
         theme.apply(&cc.egui_ctx);
-        
+
+        let command_handler = CommandHandler::new();
+        let plugin_commands = command_handler.plugin_command_names();
+
         Self {
-            terminal: Terminal::new().expect("Failed to initialize terminal"),
+            terminal: Terminal::new(theme.current_theme(), &plugin_commands).expect("Failed to initialize terminal"),
             theme,
-            command_handler: CommandHandler::new(),
+            command_handler,
         }
     }
 }
@@ -23,9 +26,13 @@ impl IgnisApp {
 impl eframe::App for IgnisApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.theme.apply(ctx);
-        
+
         egui::CentralPanel::default().show(ctx, |ui| {
-            self.terminal.show(ui, &mut self.command_handler);
+            self.terminal.show(ui, &mut self.command_handler, &mut self.theme);
         });
+
+        // Keep repainting so PTY output streaming in on a background thread
+        // gets flushed to the screen even without user input.
+        ctx.request_repaint();
     }
-}
\ No newline at end of file
+}
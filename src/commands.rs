@@ -1,6 +1,10 @@
 use std::process::{Command, Output};
 use thiserror::Error;
 
+use crate::ansi::{self, Row};
+use crate::plugin::PluginManager;
+use crate::theme::{Theme, ThemeManager};
+
 #[derive(Error, Debug)]
 pub enum CommandError {
     #[error("Command execution failed")]
@@ -9,48 +13,83 @@ pub enum CommandError {
     NotFound,
 }
 
-pub struct CommandHandler;
+pub struct CommandHandler {
+    plugins: PluginManager,
+}
 
 impl CommandHandler {
     pub fn new() -> Self {
-        Self
+        Self { plugins: PluginManager::load() }
     }
 
-    pub fn execute(&self, command: &str) -> String {
+    /// Command names advertised by configured plugins, to seed the
+    /// completer at startup.
+    pub fn plugin_command_names(&self) -> Vec<String> {
+        self.plugins.command_names()
+    }
+
+    pub fn execute(&mut self, command: &str, theme_manager: &mut ThemeManager) -> Vec<Row> {
         if command.is_empty() {
-            return String::new();
+            return Vec::new();
+        }
+
+        if let Some(rest) = command.strip_prefix(":theme") {
+            return self.theme_command(rest.trim(), theme_manager);
         }
 
         match command {
-            "clear" => return String::new(),
+            "clear" => return Vec::new(),
             "exit" => std::process::exit(0),
             _ => {}
         }
 
+        let (name, args) = command.split_once(' ').unwrap_or((command, ""));
+        if let Some(output) = self.plugins.invoke(name, args) {
+            return ansi::parse(output.as_bytes(), theme_manager.current_theme());
+        }
+
         #[cfg(unix)] {
-            use std::os::unix::process::CommandExt;
             match Command::new("sh").arg("-c").arg(command).output() {
-                Ok(output) => self.format_output(&output),
-                Err(e) => format!("Error: {}", e),
+                Ok(output) => self.format_output(&output, theme_manager.current_theme()),
+                Err(e) => ansi::parse(format!("Error: {}", e).as_bytes(), theme_manager.current_theme()),
             }
         }
-        
+
         #[cfg(windows)] {
             match Command::new("cmd").arg("/C").arg(command).output() {
-                Ok(output) => self.format_output(&output),
-                Err(e) => format!("Error: {}", e),
+                Ok(output) => self.format_output(&output, theme_manager.current_theme()),
+                Err(e) => ansi::parse(format!("Error: {}", e).as_bytes(), theme_manager.current_theme()),
             }
         }
     }
 
-    fn format_output(&self, output: &Output) -> String {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        
-        if !stderr.is_empty() {
-            format!("Error:\n{}", stderr)
+    /// Handles `:theme`, `:theme next` and `:theme <name>` before anything
+    /// reaches the shell, so switching themes doesn't depend on an external
+    /// `theme` binary existing on `PATH`.
+    fn theme_command(&self, name: &str, theme_manager: &mut ThemeManager) -> Vec<Row> {
+        if name.is_empty() {
+            let names = theme_manager.theme_names().join(", ");
+            return ansi::parse(format!("Available themes: {}", names).as_bytes(), theme_manager.current_theme());
+        }
+
+        if name == "next" {
+            theme_manager.next_theme();
+            let name = theme_manager.current_theme().name.clone();
+            return ansi::parse(format!("Switched to theme '{}'", name).as_bytes(), theme_manager.current_theme());
+        }
+
+        if theme_manager.set_theme(name) {
+            ansi::parse(format!("Switched to theme '{}'", name).as_bytes(), theme_manager.current_theme())
         } else {
-            stdout.to_string()
+            ansi::parse(format!("No such theme: '{}'", name).as_bytes(), theme_manager.current_theme())
         }
     }
-}
\ No newline at end of file
+
+    fn format_output(&self, output: &Output, theme: &Theme) -> Vec<Row> {
+        if !output.stderr.is_empty() {
+            ansi::parse(&output.stderr, theme)
+        } else {
+            ansi::parse(&output.stdout, theme)
+        }
+    }
+}
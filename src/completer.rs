@@ -28,6 +28,16 @@ impl IgnisCompleter {
             .cloned()
             .collect()
     }
+
+    /// Registers additional command names (e.g. advertised by a plugin) so
+    /// they show up alongside the built-in commands.
+    pub fn add_commands(&mut self, names: &[String]) {
+        for name in names {
+            if !self.commands.contains(name) {
+                self.commands.push(name.clone());
+            }
+        }
+    }
 }
 
 impl Completer for IgnisCompleter {
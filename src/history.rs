@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// Cap on persisted entries so the history file doesn't grow forever.
+const MAX_ENTRIES: usize = 1000;
+
+/// Persistent command history, kept separate from the terminal's
+/// scrollback display and stored one entry per line in
+/// `~/.config/ignis/history`.
+pub struct CommandHistory {
+    entries: Vec<String>,
+    path: Option<PathBuf>,
+}
+
+impl CommandHistory {
+    pub fn load() -> Self {
+        let path = dirs::config_dir().map(|dir| dir.join("ignis").join("history"));
+
+        let entries = path.as_ref()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .map(|contents| contents.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+
+        Self { entries, path }
+    }
+
+    /// Appends a command, skipping consecutive duplicates, and persists
+    /// the (capped) history to disk.
+    pub fn push(&mut self, command: &str) {
+        if self.entries.last().map(String::as_str) == Some(command) {
+            return;
+        }
+
+        self.entries.push(command.to_string());
+        if self.entries.len() > MAX_ENTRIES {
+            let overflow = self.entries.len() - MAX_ENTRIES;
+            self.entries.drain(0..overflow);
+        }
+
+        self.save();
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(path, self.entries.join("\n"));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Matches containing `query` as a substring, most recent first.
+    pub fn search(&self, query: &str) -> Vec<&str> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        self.entries.iter().rev()
+            .filter(|entry| entry.contains(query))
+            .map(String::as_str)
+            .collect()
+    }
+}
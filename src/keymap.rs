@@ -0,0 +1,115 @@
+use eframe::egui;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Terminal-level actions a chord can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Action {
+    RunCommand,
+    Complete,
+    Interrupt,
+    Clear,
+    HistoryBack,
+    HistoryForward,
+    ReverseSearch,
+    ScrollUp,
+    ScrollDown,
+    Quit,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    keybinds: HashMap<String, Action>,
+}
+
+/// The embedded default keymap, in the same `Config(keybinds: { ... })`
+/// RON shape users write `~/.config/ignis/keybinds.ron` overrides in.
+///
+/// Letter chords (`Ctrl-c`, `Ctrl-r`, ...) must stay lowercase here to match
+/// `Keybinds::key_name`, which lowercases single-letter key names.
+const DEFAULT_KEYBINDS: &str = r#"
+Config(
+    keybinds: {
+        "<Enter>": RunCommand,
+        "<Tab>": Complete,
+        "<Ctrl-c>": Interrupt,
+        "<Ctrl-l>": Clear,
+        "<up>": HistoryBack,
+        "<down>": HistoryForward,
+        "<Ctrl-r>": ReverseSearch,
+        "<PageUp>": ScrollUp,
+        "<PageDown>": ScrollDown,
+        "<Ctrl-q>": Quit,
+    },
+)
+"#;
+
+pub struct Keybinds {
+    bindings: HashMap<String, Action>,
+}
+
+impl Keybinds {
+    /// Loads the embedded defaults, then merges in
+    /// `~/.config/ignis/keybinds.ron` on top if present.
+    pub fn load() -> Self {
+        let mut bindings = ron::from_str::<Config>(DEFAULT_KEYBINDS)
+            .expect("embedded default keybinds are valid RON")
+            .keybinds;
+
+        if let Some(config_dir) = dirs::config_dir() {
+            let path = config_dir.join("ignis").join("keybinds.ron");
+            if let Ok(contents) = std::fs::read_to_string(&path) {
+                match ron::from_str::<Config>(&contents) {
+                    Ok(user) => bindings.extend(user.keybinds),
+                    Err(e) => eprintln!("Ignoring malformed keybinds file: {}", e),
+                }
+            }
+        }
+
+        Self { bindings }
+    }
+
+    pub fn action_for(&self, chord: &str) -> Option<Action> {
+        self.bindings.get(chord).copied()
+    }
+
+    /// Renders an egui key press + modifier state into the `"<Ctrl-c>"`
+    /// style chord strings used as keys in the keymap.
+    pub fn chord_for(key: egui::Key, modifiers: egui::Modifiers) -> String {
+        let mut chord = String::from("<");
+        if modifiers.ctrl {
+            chord.push_str("Ctrl-");
+        }
+        if modifiers.alt {
+            chord.push_str("Alt-");
+        }
+        if modifiers.shift {
+            chord.push_str("Shift-");
+        }
+        chord.push_str(&Self::key_name(key));
+        chord.push('>');
+        chord
+    }
+
+    fn key_name(key: egui::Key) -> String {
+        match key {
+            egui::Key::ArrowUp => "up".to_string(),
+            egui::Key::ArrowDown => "down".to_string(),
+            egui::Key::ArrowLeft => "left".to_string(),
+            egui::Key::ArrowRight => "right".to_string(),
+            other => {
+                // Single-letter keys (`Key::C`, `Key::R`, ...) debug-format
+                // as uppercase, but the keybind chords are written lowercase
+                // (`"<Ctrl-c>"`); lowercase those so they still match. Named
+                // keys (`Enter`, `PageUp`, ...) already match their chord
+                // spelling and are left alone.
+                let name = format!("{:?}", other);
+                if name.chars().count() == 1 {
+                    name.to_lowercase()
+                } else {
+                    name
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How long to wait for a plugin's reply before giving up on it. A plugin
+/// that hangs past this runs out its round-trip on a background thread
+/// instead of freezing the UI thread that's waiting on it.
+const PLUGIN_CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A JSON-RPC 2.0 request sent to a plugin over its stdin.
+#[derive(Serialize)]
+struct JsonRpc {
+    jsonrpc: &'static str,
+    method: String,
+    params: Value,
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse {
+    #[serde(default)]
+    result: Option<Value>,
+    #[serde(default)]
+    #[allow(dead_code)]
+    error: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct PluginsConfig {
+    #[serde(default)]
+    plugins: Vec<PathBuf>,
+}
+
+/// One long-lived plugin child process, talking JSON-RPC over its stdio.
+/// `stdin`/`stdout` are behind a `Mutex` so a round-trip can run on its own
+/// thread (see `call`) without the `Plugin` itself needing to move.
+struct Plugin {
+    #[allow(dead_code)]
+    child: Child,
+    stdin: Arc<Mutex<ChildStdin>>,
+    stdout: Arc<Mutex<BufReader<ChildStdout>>>,
+    commands: Vec<String>,
+    alive: bool,
+}
+
+/// Registers external executables as shell commands. Each plugin is
+/// launched once, asked for its `config` (the command names/signatures it
+/// provides), and then kept running for the lifetime of the app — a typed
+/// command matching one of its names is forwarded as an `invoke` request
+/// instead of being handed to the shell.
+pub struct PluginManager {
+    plugins: Vec<Plugin>,
+    next_id: u64,
+}
+
+impl PluginManager {
+    /// Reads `~/.config/ignis/plugins.toml` (a `plugins = ["/path/to/bin"]`
+    /// list) and spawns each entry.
+    pub fn load() -> Self {
+        let mut manager = Self { plugins: Vec::new(), next_id: 1 };
+
+        let Some(config_dir) = dirs::config_dir() else { return manager };
+        let config_path = config_dir.join("ignis").join("plugins.toml");
+        let Ok(contents) = std::fs::read_to_string(&config_path) else { return manager };
+
+        let config = match toml::from_str::<PluginsConfig>(&contents) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Ignoring malformed plugins config: {}", e);
+                return manager;
+            }
+        };
+
+        for path in config.plugins {
+            match manager.spawn(&path) {
+                Ok(plugin) => manager.plugins.push(plugin),
+                Err(e) => eprintln!("Failed to start plugin {}: {}", path.display(), e),
+            }
+        }
+
+        manager
+    }
+
+    fn spawn(&mut self, path: &PathBuf) -> std::io::Result<Plugin> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let stdin = Arc::new(Mutex::new(child.stdin.take().expect("plugin spawned with piped stdin")));
+        let stdout = Arc::new(Mutex::new(BufReader::new(child.stdout.take().expect("plugin spawned with piped stdout"))));
+
+        let mut plugin = Plugin { child, stdin, stdout, commands: Vec::new(), alive: true };
+
+        let id = self.next_id();
+        match Self::call(&mut plugin, "config", Value::Null, id) {
+            Ok(Some(result)) => {
+                plugin.commands = result.get("commands")
+                    .and_then(Value::as_array)
+                    .map(|names| names.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+                    .unwrap_or_default();
+            }
+            _ => plugin.alive = false,
+        }
+
+        Ok(plugin)
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Runs one JSON-RPC round-trip on a background thread and waits up to
+    /// `PLUGIN_CALL_TIMEOUT` for the reply. If the plugin never answers, the
+    /// thread is left to finish (or hang) on its own time and this returns a
+    /// timeout error instead of blocking the caller forever.
+    fn call(plugin: &mut Plugin, method: &str, params: Value, id: u64) -> std::io::Result<Option<Value>> {
+        let request = JsonRpc { jsonrpc: "2.0", method: method.to_string(), params, id };
+        let request = serde_json::to_string(&request)?;
+
+        let stdin = Arc::clone(&plugin.stdin);
+        let stdout = Arc::clone(&plugin.stdout);
+        let (tx, rx) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let result = (|| -> std::io::Result<Option<Value>> {
+                {
+                    let mut stdin = stdin.lock().unwrap();
+                    writeln!(stdin, "{}", request)?;
+                    stdin.flush()?;
+                }
+
+                let mut line = String::new();
+                if stdout.lock().unwrap().read_line(&mut line)? == 0 {
+                    return Ok(None);
+                }
+
+                let response: JsonRpcResponse = serde_json::from_str(&line)?;
+                Ok(response.result)
+            })();
+            let _ = tx.send(result);
+        });
+
+        match rx.recv_timeout(PLUGIN_CALL_TIMEOUT) {
+            Ok(result) => {
+                if matches!(result, Ok(None)) {
+                    plugin.alive = false;
+                }
+                result
+            }
+            Err(_) => Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "plugin call timed out")),
+        }
+    }
+
+    /// Command names advertised by all currently-live plugins, to feed the
+    /// completer.
+    pub fn command_names(&self) -> Vec<String> {
+        self.plugins.iter()
+            .filter(|p| p.alive)
+            .flat_map(|p| p.commands.iter().cloned())
+            .collect()
+    }
+
+    /// If `command` was registered by a live plugin, invokes it and returns
+    /// its output; returns `None` when no plugin claims this command so the
+    /// caller can fall back to the shell.
+    pub fn invoke(&mut self, command: &str, args: &str) -> Option<String> {
+        let id = self.next_id();
+        let plugin = self.plugins.iter_mut()
+            .find(|p| p.alive && p.commands.iter().any(|c| c == command))?;
+
+        let params = serde_json::json!({ "command": command, "args": args });
+        match Self::call(plugin, "invoke", params, id) {
+            Ok(Some(result)) => Some(
+                result.get("output").and_then(Value::as_str).unwrap_or_default().to_string(),
+            ),
+            Ok(None) => Some(format!("Plugin '{}' crashed", command)),
+            Err(e) => {
+                plugin.alive = false;
+                Some(format!("Plugin '{}' error: {}", command, e))
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
 #[cfg(unix)]
 mod unix_pty {
     use nix::{
-        unistd::{fork, ForkResult},
+        unistd::{fork, ForkResult, Pid},
         fcntl::{self, OFlag},
         sys::stat::Mode,
+        sys::signal::{self, Signal},
         errno::Errno,
     };
     use std::os::unix::io::RawFd;
+    use std::sync::mpsc::{self, Receiver, TryRecvError};
     use thiserror::Error;
 
     #[derive(Error, Debug)]
@@ -19,6 +21,8 @@ mod unix_pty {
 
     pub struct Pty {
         master: RawFd,
+        child: Pid,
+        output_rx: Receiver<Vec<u8>>,
     }
 
     impl Pty {
@@ -36,8 +40,21 @@ mod unix_pty {
             }
 
             match fork()? {
-                ForkResult::Parent { child: _ } => Ok(Self { master }),
+                ForkResult::Parent { child } => {
+                    let flags = fcntl::fcntl(master, fcntl::FcntlArg::F_GETFL)?;
+                    let flags = OFlag::from_bits_truncate(flags) | OFlag::O_NONBLOCK;
+                    fcntl::fcntl(master, fcntl::FcntlArg::F_SETFL(flags))?;
+
+                    let output_rx = Self::spawn_reader(master);
+                    Ok(Self { master, child, output_rx })
+                }
                 ForkResult::Child => {
+                    // Start a new session so the slave we're about to open
+                    // becomes our controlling terminal. Without this the
+                    // shell never gets a foreground process group, and the
+                    // line discipline has nowhere to deliver INTR/TSTP to.
+                    let _ = nix::unistd::setsid();
+
                     let slave_name = unsafe {
                         std::ffi::CStr::from_ptr(libc::ptsname(master))
                             .to_string_lossy()
@@ -62,13 +79,101 @@ mod unix_pty {
             }
         }
 
+        /// Reads the master side on a background thread so interactive and
+        /// long-running programs (top, vim, a REPL) stream their output
+        /// incrementally instead of only after they exit.
+        fn spawn_reader(master: RawFd) -> Receiver<Vec<u8>> {
+            let (tx, rx) = mpsc::channel();
+
+            std::thread::spawn(move || {
+                let mut buf = [0u8; 4096];
+                loop {
+                    match nix::unistd::read(master, &mut buf) {
+                        Ok(0) => break,
+                        Ok(n) => {
+                            if tx.send(buf[..n].to_vec()).is_err() {
+                                break;
+                            }
+                        }
+                        Err(Errno::EAGAIN) => {
+                            std::thread::sleep(std::time::Duration::from_millis(10));
+                        }
+                        Err(_) => break,
+                    }
+                }
+            });
+
+            rx
+        }
+
+        /// Drains one pending chunk of PTY output, if any is buffered.
+        /// Returns `None` once the channel is empty for this frame.
+        pub fn try_recv(&self) -> Option<Vec<u8>> {
+            match self.output_rx.try_recv() {
+                Ok(chunk) => Some(chunk),
+                Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => None,
+            }
+        }
+
         pub fn send_command(&self, command: &str) {
-            unsafe {
-                let mut file = std::fs::File::from_raw_fd(self.master);
-                let _ = file.write_all(command.as_bytes());
-                let _ = file.write_all(b"\n");
-                std::mem::forget(file);
+            let mut payload = command.as_bytes().to_vec();
+            payload.push(b'\n');
+            if let Err(e) = self.write_all(&payload) {
+                eprintln!("Failed to write to PTY: {}", e);
+            }
+        }
+
+        /// Writes the full buffer to the master side, retrying on `EAGAIN`.
+        /// The master fd is `O_NONBLOCK` (for the reader thread sharing the
+        /// same file description), so a plain `write` can come back short
+        /// or with `WouldBlock` under backpressure instead of blocking.
+        fn write_all(&self, mut buf: &[u8]) -> std::io::Result<()> {
+            while !buf.is_empty() {
+                match nix::unistd::write(self.master, buf) {
+                    Ok(0) => break,
+                    Ok(n) => buf = &buf[n..],
+                    Err(Errno::EAGAIN) => std::thread::sleep(std::time::Duration::from_millis(5)),
+                    Err(e) => return Err(std::io::Error::from(e)),
+                }
             }
+            Ok(())
+        }
+
+        /// Tells the child's controlling terminal its new size via `TIOCSWINSZ`
+        /// so full-screen programs redraw correctly after a panel resize.
+        pub fn resize(&self, cols: u16, rows: u16) -> Result<(), PtyError> {
+            let winsize = libc::winsize {
+                ws_row: rows,
+                ws_col: cols,
+                ws_xpixel: 0,
+                ws_ypixel: 0,
+            };
+
+            let ret = unsafe { libc::ioctl(self.master, libc::TIOCSWINSZ, &winsize) };
+            if ret != 0 {
+                return Err(PtyError::OperationFailed(nix::Error::last()));
+            }
+            Ok(())
+        }
+
+        /// Mirrors what a real terminal does on `Ctrl-C`: writes the line
+        /// discipline's INTR byte (0x03) to the master side so the kernel
+        /// signals whichever process the shell has placed in the terminal's
+        /// foreground process group. Signaling `self.child` directly would
+        /// only ever reach the forked shell itself, not a command it's
+        /// currently running in the foreground (`sleep`, `vim`, a REPL).
+        pub fn interrupt(&self) -> Result<(), PtyError> {
+            nix::unistd::write(self.master, &[0x03])?;
+            Ok(())
+        }
+    }
+
+    impl Drop for Pty {
+        /// Hangs up and reaps the forked shell so it doesn't linger as a
+        /// zombie once its `Pty` goes away.
+        fn drop(&mut self) {
+            let _ = signal::kill(self.child, Signal::SIGHUP);
+            let _ = nix::sys::wait::waitpid(self.child, None);
         }
     }
 }
@@ -91,6 +196,18 @@ mod windows_pty {
         }
 
         pub fn send_command(&self, _command: &str) {}
+
+        pub fn try_recv(&self) -> Option<Vec<u8>> {
+            None
+        }
+
+        pub fn resize(&self, _cols: u16, _rows: u16) -> Result<(), PtyError> {
+            Err(PtyError::Unsupported)
+        }
+
+        pub fn interrupt(&self) -> Result<(), PtyError> {
+            Err(PtyError::Unsupported)
+        }
     }
 }
 
@@ -98,4 +215,4 @@ mod windows_pty {
 pub use unix_pty::{Pty, PtyError};
 
 #[cfg(windows)]
-pub use windows_pty::{Pty, PtyError};
\ No newline at end of file
+pub use windows_pty::{Pty, PtyError};
@@ -1,23 +1,48 @@
 use eframe::egui;
-use crate::{commands::CommandHandler, completer::IgnisCompleter, pty::Pty};
+use crate::{
+    ansi,
+    commands::CommandHandler,
+    completer::IgnisCompleter,
+    history::CommandHistory,
+    keymap::{Action, Keybinds},
+    pty::Pty,
+    theme::{Theme, ThemeManager},
+};
 
 pub struct Terminal {
     input: String,
-    history: Vec<String>,
+    grid: ansi::AnsiParser,
     cursor_pos: usize,
     completer: IgnisCompleter,
     show_completions: bool,
+    keybinds: Keybinds,
+    history: CommandHistory,
+    history_index: Option<usize>,
+    reverse_search: Option<String>,
+    pending_scroll: Option<f32>,
     #[cfg(unix)] pty: Option<Pty>,  // Only include on Unix
+    #[cfg(unix)] pty_size: (u16, u16),
 }
 
 impl Terminal {
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(theme: &Theme, plugin_commands: &[String]) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut grid = ansi::AnsiParser::new(theme);
+        grid.feed(b"Welcome to Ignis Terminal\n");
+
+        let mut completer = IgnisCompleter::new();
+        completer.add_commands(plugin_commands);
+
         Ok(Self {
             input: String::new(),
-            history: vec!["Welcome to Ignis Terminal".to_string()],
+            grid,
             cursor_pos: 0,
-            completer: IgnisCompleter::new(),
+            completer,
             show_completions: false,
+            keybinds: Keybinds::load(),
+            history: CommandHistory::load(),
+            history_index: None,
+            reverse_search: None,
+            pending_scroll: None,
             #[cfg(unix)] pty: match Pty::new() {
                 Ok(pty) => Some(pty),
                 Err(e) => {
@@ -25,18 +50,27 @@ impl Terminal {
                     None
                 }
             },
+            #[cfg(unix)] pty_size: (0, 0),
         })
     }
 
-    pub fn show(&mut self, ui: &mut egui::Ui, command_handler: &mut CommandHandler) {
-        // Display command history
+    pub fn show(&mut self, ui: &mut egui::Ui, command_handler: &mut CommandHandler, theme_manager: &mut ThemeManager) {
+        #[cfg(unix)]
+        self.poll_pty();
+
+        if let Some(delta) = self.pending_scroll.take() {
+            ui.scroll_with_delta(egui::vec2(0.0, delta));
+        }
+
+        // Display the live scrollback grid
         egui::ScrollArea::vertical().show(ui, |ui| {
-            for line in &self.history {
-                ui.label(line);
+            for row in self.grid.rows() {
+                ui.label(Self::row_to_job(row));
             }
         });
 
         // Command input
+        let mut response_has_focus = false;
         ui.horizontal(|ui| {
             ui.label("> ");
             let response = ui.add(
@@ -44,16 +78,28 @@ impl Terminal {
                     .desired_width(f32::INFINITY)
                     .hint_text("Enter command..."),
             );
-
-            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                self.execute_command(command_handler);
-            }
-
-            if response.has_focus() && ui.input(|i| i.key_pressed(egui::Key::Tab)) {
-                self.show_completions = true;
+            // A singleline TextEdit surrenders focus the instant Enter is
+            // pressed, so `has_focus()` alone would already read false on
+            // the very frame `RunCommand` needs to fire. Treat the frame it
+            // just lost focus as still-focused for dispatch purposes.
+            response_has_focus = response.has_focus() || response.lost_focus();
+            if response.changed() {
+                self.history_index = None;
             }
         });
 
+        let actions = self.pending_actions(ui);
+        for action in actions {
+            self.dispatch_action(action, command_handler, theme_manager, response_has_focus);
+        }
+
+        #[cfg(unix)]
+        self.resize_pty(ui);
+
+        if self.reverse_search.is_some() {
+            self.show_reverse_search(ui);
+        }
+
         // Show autocomplete suggestions
         if self.show_completions && !self.input.is_empty() {
             let suggestions = self.completer.complete_command(&self.input);
@@ -72,18 +118,196 @@ impl Terminal {
         }
     }
 
-    fn execute_command(&mut self, command_handler: &mut CommandHandler) {
+    /// Translates this frame's key-press events into keymap actions.
+    fn pending_actions(&self, ui: &egui::Ui) -> Vec<Action> {
+        ui.input(|input| {
+            input.events.iter().filter_map(|event| match event {
+                egui::Event::Key { key, pressed: true, modifiers, .. } => {
+                    let chord = Keybinds::chord_for(*key, *modifiers);
+                    self.keybinds.action_for(&chord)
+                }
+                _ => None,
+            }).collect()
+        })
+    }
+
+    fn dispatch_action(
+        &mut self,
+        action: Action,
+        command_handler: &mut CommandHandler,
+        theme_manager: &mut ThemeManager,
+        input_focused: bool,
+    ) {
+        match action {
+            Action::RunCommand if input_focused => self.execute_command(command_handler, theme_manager),
+            Action::Complete if input_focused => self.show_completions = true,
+            Action::Interrupt => self.send_interrupt(),
+            Action::Clear => self.clear_screen(theme_manager.current_theme()),
+            Action::HistoryBack if input_focused => self.history_back(),
+            Action::HistoryForward if input_focused => self.history_forward(),
+            Action::ReverseSearch if input_focused => self.reverse_search = Some(String::new()),
+            Action::ScrollUp => self.pending_scroll = Some(40.0),
+            Action::ScrollDown => self.pending_scroll = Some(-40.0),
+            Action::Quit => std::process::exit(0),
+            _ => {}
+        }
+    }
+
+    fn send_interrupt(&self) {
+        #[cfg(unix)]
+        if let Some(pty) = &self.pty {
+            let _ = pty.interrupt();
+        }
+    }
+
+    fn clear_screen(&mut self, theme: &Theme) {
+        self.grid = ansi::AnsiParser::new(theme);
+    }
+
+    fn history_back(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let next_index = match self.history_index {
+            Some(i) => i.saturating_sub(1),
+            None => self.history.len() - 1,
+        };
+        self.history_index = Some(next_index);
+        self.input = self.history.get(next_index).unwrap_or_default().to_string();
+    }
+
+    fn history_forward(&mut self) {
+        let Some(index) = self.history_index else { return };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.input = self.history.get(index + 1).unwrap_or_default().to_string();
+        } else {
+            self.history_index = None;
+            self.input.clear();
+        }
+    }
+
+    /// Incremental Ctrl-R reverse search: filters history by substring as
+    /// the user types, accepting the top match into the input on Enter.
+    fn show_reverse_search(&mut self, ui: &egui::Ui) {
+        let Some(query) = self.reverse_search.clone() else { return };
+        let mut query = query;
+        let mut accept: Option<String> = None;
+        let mut close = false;
+
+        egui::Window::new("Reverse search (Ctrl-R)")
+            .auto_sized()
+            .show(ui.ctx(), |ui| {
+                let response = ui.text_edit_singleline(&mut query);
+                response.request_focus();
+
+                let matches = self.history.search(&query);
+                for m in matches.iter().take(10) {
+                    if ui.button(*m).clicked() {
+                        accept = Some((*m).to_string());
+                    }
+                }
+
+                if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    accept = accept.or_else(|| matches.first().map(|m| m.to_string()));
+                }
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    close = true;
+                }
+            });
+
+        self.reverse_search = Some(query);
+
+        if let Some(command) = accept {
+            self.input = command;
+            self.reverse_search = None;
+        } else if close {
+            self.reverse_search = None;
+        }
+    }
+
+    /// Drain whatever the PTY's reader thread has buffered this frame and
+    /// feed it straight into the scrollback grid.
+    #[cfg(unix)]
+    fn poll_pty(&mut self) {
+        if let Some(pty) = &self.pty {
+            while let Some(chunk) = pty.try_recv() {
+                self.grid.feed(&chunk);
+            }
+        }
+    }
+
+    /// Recompute the PTY's size from the panel's glyph metrics and push it
+    /// down via `TIOCSWINSZ` whenever it changes, so full-screen programs
+    /// (top, vim) redraw at the right dimensions.
+    #[cfg(unix)]
+    fn resize_pty(&mut self, ui: &egui::Ui) {
+        let Some(pty) = &self.pty else { return };
+
+        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+        let glyph_width = ui.fonts(|fonts| fonts.glyph_width(&font_id, 'M'));
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        if glyph_width <= 0.0 || row_height <= 0.0 {
+            return;
+        }
+
+        let cols = (ui.available_width() / glyph_width).floor().max(1.0) as u16;
+        let rows = (ui.available_height() / row_height).floor().max(1.0) as u16;
+
+        if (cols, rows) != self.pty_size && pty.resize(cols, rows).is_ok() {
+            self.pty_size = (cols, rows);
+        }
+    }
+
+    fn execute_command(&mut self, command_handler: &mut CommandHandler, theme_manager: &mut ThemeManager) {
         let command = self.input.trim().to_string();
-        if !command.is_empty() {
-            self.history.push(format!("> {}", command));
-            
-            let output = command_handler.execute(&command);
-            if !output.is_empty() {
-                self.history.push(output);
+        if command.is_empty() {
+            return;
+        }
+
+        self.history.push(&command);
+        self.history_index = None;
+
+        // Built-in commands (e.g. `:theme`) and plugin-provided commands are
+        // handled locally even when a PTY backend is active, so they never
+        // get sent to the shell.
+        #[cfg(unix)]
+        if let Some(pty) = &self.pty {
+            let name = command.split_once(' ').map_or(command.as_str(), |(name, _)| name);
+            let is_plugin = command_handler.plugin_command_names().iter().any(|c| c == name);
+            if !command.starts_with(':') && !is_plugin {
+                pty.send_command(&command);
+                self.input.clear();
+                self.show_completions = false;
+                return;
             }
-            
-            self.input.clear();
-            self.show_completions = false;
         }
+
+        self.grid.feed(format!("> {}\n", command).as_bytes());
+        let output = command_handler.execute(&command, theme_manager);
+        if !output.is_empty() {
+            self.grid.append_rows(output);
+        }
+
+        self.input.clear();
+        self.show_completions = false;
+    }
+
+    /// Lay out one parsed row as a styled `LayoutJob` for `ui.label`.
+    fn row_to_job(row: &ansi::Row) -> egui::text::LayoutJob {
+        let mut job = egui::text::LayoutJob::default();
+        for cell in &row.cells {
+            job.append(
+                &cell.ch.to_string(),
+                0.0,
+                egui::TextFormat {
+                    color: cell.fg,
+                    background: cell.bg,
+                    italics: cell.italic,
+                    ..Default::default()
+                },
+            );
+        }
+        job
     }
-}
\ No newline at end of file
+}
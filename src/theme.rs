@@ -1,6 +1,7 @@
 use eframe::egui;
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
+use std::path::Path;
 
 #[derive(Clone)]
 pub struct Theme {
@@ -11,24 +12,27 @@ pub struct Theme {
     pub syntax_highlighting: HashMap<String, egui::Color32>,
 }
 
+/// On-disk form of a `Theme`, as authored in `~/.config/ignis/themes/*.toml`.
+/// Colors are `#rrggbb` hex strings, matching the convention used by Helix
+/// and most terminal theme formats.
 #[derive(Serialize, Deserialize, Clone)]
 struct SerializableTheme {
     name: String,
-    background: [u8; 4],
-    foreground: [u8; 4],
-    accent: [u8; 4],
-    syntax_highlighting: HashMap<String, [u8; 4]>,
+    background: String,
+    foreground: String,
+    accent: String,
+    syntax_highlighting: HashMap<String, String>,
 }
 
 impl Theme {
     pub fn to_serializable(&self) -> SerializableTheme {
         SerializableTheme {
             name: self.name.clone(),
-            background: self.background.to_array(),
-            foreground: self.foreground.to_array(),
-            accent: self.accent.to_array(),
+            background: Self::color_to_hex(self.background),
+            foreground: Self::color_to_hex(self.foreground),
+            accent: Self::color_to_hex(self.accent),
             syntax_highlighting: self.syntax_highlighting.iter()
-                .map(|(k, v)| (k.clone(), v.to_array()))
+                .map(|(k, v)| (k.clone(), Self::color_to_hex(*v)))
                 .collect(),
         }
     }
@@ -36,29 +40,28 @@ impl Theme {
     pub fn from_serializable(serializable: SerializableTheme) -> Self {
         Theme {
             name: serializable.name,
-            background: egui::Color32::from_rgba_premultiplied(
-                serializable.background[0],
-                serializable.background[1],
-                serializable.background[2],
-                serializable.background[3],
-            ),
-            foreground: egui::Color32::from_rgba_premultiplied(
-                serializable.foreground[0],
-                serializable.foreground[1],
-                serializable.foreground[2],
-                serializable.foreground[3],
-            ),
-            accent: egui::Color32::from_rgba_premultiplied(
-                serializable.accent[0],
-                serializable.accent[1],
-                serializable.accent[2],
-                serializable.accent[3],
-            ),
+            background: Self::hex_to_color(&serializable.background),
+            foreground: Self::hex_to_color(&serializable.foreground),
+            accent: Self::hex_to_color(&serializable.accent),
             syntax_highlighting: serializable.syntax_highlighting.into_iter()
-                .map(|(k, v)| (k, egui::Color32::from_rgba_premultiplied(v[0], v[1], v[2], v[3])))
+                .map(|(k, v)| (k, Self::hex_to_color(&v)))
                 .collect(),
         }
     }
+
+    fn color_to_hex(color: egui::Color32) -> String {
+        format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+    }
+
+    fn hex_to_color(hex: &str) -> egui::Color32 {
+        let hex = hex.trim_start_matches('#');
+        let value = u32::from_str_radix(hex, 16).unwrap_or(0);
+        egui::Color32::from_rgb(
+            ((value >> 16) & 0xff) as u8,
+            ((value >> 8) & 0xff) as u8,
+            (value & 0xff) as u8,
+        )
+    }
 }
 
 pub struct ThemeManager {
@@ -86,11 +89,59 @@ impl ThemeManager {
         }
     }
 
+    /// Builds the default theme, then scans `~/.config/ignis/themes/*.toml`
+    /// for user themes and adds them to the list, leaving "Default Dark"
+    /// active until the user switches with `:theme <name>`.
+    pub fn load() -> Self {
+        let mut manager = Self::load_default();
+
+        if let Some(config_dir) = dirs::config_dir() {
+            let themes_dir = config_dir.join("ignis").join("themes");
+            manager.themes.extend(Self::load_from_dir(&themes_dir));
+        }
+
+        manager
+    }
+
+    fn load_from_dir(dir: &Path) -> Vec<Theme> {
+        let mut themes = Vec::new();
+
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return themes;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+                continue;
+            }
+
+            let parsed = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|contents| toml::from_str::<SerializableTheme>(&contents).ok());
+
+            match parsed {
+                Some(serializable) => themes.push(Theme::from_serializable(serializable)),
+                None => eprintln!("Ignoring malformed theme file: {}", path.display()),
+            }
+        }
+
+        themes
+    }
+
     pub fn apply(&self, ctx: &egui::Context) {
+        let theme = &self.current_theme;
         let mut visuals = egui::Visuals::dark();
-        visuals.widgets.noninteractive.bg_fill = self.current_theme.background;
-        visuals.widgets.noninteractive.fg_stroke.color = self.current_theme.foreground;
-        visuals.widgets.active.bg_fill = self.current_theme.accent;
+        visuals.widgets.noninteractive.bg_fill = theme.background;
+        visuals.widgets.noninteractive.fg_stroke.color = theme.foreground;
+        visuals.widgets.active.bg_fill = theme.accent;
+        visuals.selection.bg_fill = theme.accent;
+        visuals.selection.stroke.color = theme.foreground;
+        visuals.hyperlink_color = theme.accent;
+        visuals.window_fill = theme.background;
+        visuals.panel_fill = theme.background;
+        visuals.extreme_bg_color = theme.background;
+        visuals.override_text_color = Some(theme.foreground);
         ctx.set_visuals(visuals);
     }
 
@@ -100,4 +151,36 @@ impl ThemeManager {
             .copied()
             .unwrap_or(self.current_theme.foreground)
     }
-}
\ No newline at end of file
+
+    pub fn current_theme(&self) -> &Theme {
+        &self.current_theme
+    }
+
+    pub fn theme_names(&self) -> Vec<&str> {
+        self.themes.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    /// Switches the active theme by name (case-insensitive). Returns `false`
+    /// without changing anything if no theme with that name is loaded.
+    pub fn set_theme(&mut self, name: &str) -> bool {
+        match self.themes.iter().find(|t| t.name.eq_ignore_ascii_case(name)) {
+            Some(theme) => {
+                self.current_theme = theme.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn next_theme(&mut self) {
+        if self.themes.is_empty() {
+            return;
+        }
+
+        let current_index = self.themes.iter()
+            .position(|t| t.name == self.current_theme.name)
+            .unwrap_or(0);
+        let next_index = (current_index + 1) % self.themes.len();
+        self.current_theme = self.themes[next_index].clone();
+    }
+}